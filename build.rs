@@ -0,0 +1,179 @@
+// build.rs - turns instructions.in into src/instrs.rs at build time
+//
+// This replaces the old hand-typed HashMap::insert ladders for
+// r_type_funcs/i_type_ops/j_type_ops/regimm_types/reg_names with one
+// generated file, so adding a new instruction is a single line in
+// instructions.in instead of edits in three different functions.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+struct Entry {
+    mnemonic: String,
+    class: String,
+    code: u32,
+    template: String,
+}
+
+fn parse_spec(text: &str) -> Vec<Entry> {
+    let mut entries = Vec::new();
+
+    for raw_line in text.lines() {
+        let line = raw_line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut fields = line.split_whitespace();
+        let mnemonic = fields.next().unwrap().to_string();
+        let class = fields.next().expect("missing class column").to_string();
+        let code_str = fields.next().expect("missing code column");
+        let template = fields.collect::<Vec<_>>().join(" ");
+
+        let code = if let Some(hex) = code_str.strip_prefix("0x") {
+            u32::from_str_radix(hex, 16).expect("bad hex code")
+        } else {
+            code_str.parse().expect("bad decimal code")
+        };
+
+        entries.push(Entry {
+            mnemonic,
+            class,
+            code,
+            template,
+        });
+    }
+
+    entries
+}
+
+fn emit_lookup_fn(out: &mut String, fn_name: &str, entries: &[&Entry]) {
+    out.push_str(&format!(
+        "fn {}(code: u32) -> Option<&'static str> {{\n    match code {{\n",
+        fn_name
+    ));
+    for e in entries {
+        out.push_str(&format!(
+            "        0x{:02X} => Some(\"{}\"),\n",
+            e.code, e.mnemonic
+        ));
+    }
+    out.push_str("        _ => None,\n    }\n}\n\n");
+}
+
+fn emit_template_fn(out: &mut String, entries: &[Entry]) {
+    out.push_str("fn operand_template(mnemonic: &str) -> Option<&'static str> {\n    match mnemonic {\n");
+    for e in entries {
+        out.push_str(&format!(
+            "        \"{}\" => Some(\"{}\"),\n",
+            e.mnemonic, e.template
+        ));
+    }
+    out.push_str("        _ => None,\n    }\n}\n\n");
+}
+
+// Reverse lookups (mnemonic -> class / code) used by the assembler to
+// re-encode an instruction into the same fields the decoder reads out of.
+fn emit_reverse_fns(out: &mut String, entries: &[Entry]) {
+    out.push_str("fn class_of(mnemonic: &str) -> Option<&'static str> {\n    match mnemonic {\n");
+    for e in entries {
+        out.push_str(&format!(
+            "        \"{}\" => Some(\"{}\"),\n",
+            e.mnemonic, e.class
+        ));
+    }
+    out.push_str("        _ => None,\n    }\n}\n\n");
+
+    out.push_str("fn code_of(mnemonic: &str) -> Option<u32> {\n    match mnemonic {\n");
+    for e in entries {
+        out.push_str(&format!(
+            "        \"{}\" => Some(0x{:02X}),\n",
+            e.mnemonic, e.code
+        ));
+    }
+    out.push_str("        _ => None,\n    }\n}\n\n");
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=instructions.in");
+
+    let spec = fs::read_to_string("instructions.in").expect("failed to read instructions.in");
+    let entries = parse_spec(&spec);
+
+    let r_type: Vec<&Entry> = entries.iter().filter(|e| e.class == "R").collect();
+    let i_type: Vec<&Entry> = entries.iter().filter(|e| e.class == "I").collect();
+    let j_type: Vec<&Entry> = entries.iter().filter(|e| e.class == "J").collect();
+    let regimm: Vec<&Entry> = entries.iter().filter(|e| e.class == "REGIMM").collect();
+    let fp_type: Vec<&Entry> = entries.iter().filter(|e| e.class == "FP").collect();
+
+    let mut out = String::new();
+    out.push_str("// GENERATED by build.rs from instructions.in - do not edit by hand.\n\n");
+
+    out.push_str("fn reg_name(n: u32) -> String {\n    format!(\"R{}\", n)\n}\n\n");
+    out.push_str("fn freg_name(n: u32) -> String {\n    format!(\"F{}\", n)\n}\n\n");
+
+    emit_lookup_fn(&mut out, "lookup_r_type", &r_type);
+    emit_lookup_fn(&mut out, "lookup_i_type", &i_type);
+    emit_lookup_fn(&mut out, "lookup_j_type", &j_type);
+    emit_lookup_fn(&mut out, "lookup_regimm", &regimm);
+    emit_lookup_fn(&mut out, "lookup_fp_type", &fp_type);
+    emit_template_fn(&mut out, &entries);
+    emit_reverse_fns(&mut out, &entries);
+
+    // Single data-driven formatter, keyed on the operand template string
+    // above instead of a per-mnemonic if/else chain.
+    out.push_str(
+        r##"fn format_operands(template: &str, f: &Fields) -> String {
+    if template == "-" {
+        return String::new();
+    }
+
+    template
+        .split(',')
+        .map(|tok| format_token(tok.trim(), f))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn format_token(tok: &str, f: &Fields) -> String {
+    if let Some(open) = tok.find('(') {
+        let outer = &tok[..open];
+        let inner = &tok[open + 1..tok.len() - 1];
+        return format!("{}({})", resolve_token(outer, f), resolve_token(inner, f));
+    }
+    resolve_token(tok, f)
+}
+
+fn resolve_token(tok: &str, f: &Fields) -> String {
+    let (prefix, name) = match tok.strip_prefix('#') {
+        Some(rest) => ("#", rest),
+        None => ("", tok),
+    };
+
+    let value = match name {
+        "rd" => reg_name(f.rd),
+        "rs" => reg_name(f.rs),
+        "rt" => reg_name(f.rt),
+        // fmt/ft/fs/fd sit in the rs/rt/rd/shamt bit positions - see
+        // parse_cop1_type - so the FP tokens just read those same fields
+        // through freg_name instead of reg_name.
+        "ft" => freg_name(f.rt),
+        "fs" => freg_name(f.rd),
+        "fd" => freg_name(f.shamt),
+        "shamt" => f.shamt.to_string(),
+        "imm" => f.imm.to_string(),
+        "uimm" => f.uimm.to_string(),
+        "addr" => f.addr.to_string(),
+        other => other.to_string(),
+    };
+
+    format!("{}{}", prefix, value)
+}
+"##,
+    );
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let dest = Path::new(&out_dir).join("instrs.rs");
+    fs::write(dest, out).expect("failed to write generated instrs.rs");
+}
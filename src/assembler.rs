@@ -0,0 +1,373 @@
+// src/assembler.rs - text MIPS -> binary words, the reverse of the
+// disassembler.
+//
+// Reuses the same mnemonic/opcode tables and operand templates from
+// instrs.rs that the decoder uses, just run the other way: parse operand
+// text out of the template instead of formatting values into it. That
+// keeps disassemble -> assemble a round trip over one shared spec.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::process;
+
+use crate::error::DisasmError;
+use crate::{class_of, code_of, operand_template, Fields, START_ADDR};
+
+pub struct MIPSAssembler {
+    input_path: String,
+    output_path: String,
+}
+
+impl MIPSAssembler {
+    pub fn new(input_path: String, output_path: String) -> Self {
+        MIPSAssembler {
+            input_path,
+            output_path,
+        }
+    }
+
+    // Read assembly source, strip blank lines
+    fn load_source(&self) -> io::Result<Vec<String>> {
+        let file = File::open(&self.input_path)?;
+        let reader = BufReader::new(file);
+
+        let mut lines = Vec::new();
+        for line in reader.lines() {
+            let line = line?;
+            let cleaned = line.trim();
+            if !cleaned.is_empty() {
+                lines.push(cleaned.to_string());
+            }
+        }
+
+        Ok(lines)
+    }
+
+    // First pass: record every "LABEL:" address, starting at START_ADDR
+    // and incrementing by 4 for each real instruction (a label-only line
+    // doesn't consume a word).
+    fn first_pass(lines: &[String]) -> HashMap<String, u32> {
+        let mut labels = HashMap::new();
+        let mut addr = START_ADDR;
+
+        for line in lines {
+            let (label, rest) = Self::split_label(line);
+            if let Some(name) = label {
+                labels.insert(name, addr);
+            }
+            if !rest.is_empty() {
+                addr += 4;
+            }
+        }
+
+        labels
+    }
+
+    // Second pass: encode each instruction now that every label resolves.
+    // A line that fails to encode (unknown mnemonic, unresolved label)
+    // doesn't abort the run - it's reported and gets a zero word in its
+    // place, the same recoverable-per-line discipline decode_instruction
+    // uses for malformed binary input, and for the same reason: dropping
+    // the word instead would shift every address after it out from under
+    // the labels first_pass already resolved.
+    fn second_pass(lines: &[String], labels: &HashMap<String, u32>) -> (Vec<String>, Vec<DisasmError>) {
+        let mut words = Vec::new();
+        let mut errors = Vec::new();
+        let mut addr = START_ADDR;
+
+        for (i, line) in lines.iter().enumerate() {
+            let (_, rest) = Self::split_label(line);
+            if rest.is_empty() {
+                continue;
+            }
+            match Self::encode_line(i + 1, rest, addr, labels) {
+                Ok(word) => words.push(word),
+                Err(e) => {
+                    errors.push(e);
+                    words.push("0".repeat(32));
+                }
+            }
+            addr += 4;
+        }
+
+        (words, errors)
+    }
+
+    // Split "LABEL: INSTR operands" (or a bare "LABEL:") into the label
+    // name and whatever instruction text follows it.
+    fn split_label(line: &str) -> (Option<String>, &str) {
+        match line.find(':') {
+            Some(idx) => (Some(line[..idx].trim().to_string()), line[idx + 1..].trim()),
+            None => (None, line),
+        }
+    }
+
+    // Encode one "MNEMONIC operand, operand, ..." line into a 32-char
+    // '0'/'1' word, using the same operand_template the decoder reads.
+    // `line` is the source line number, used only to label any error.
+    fn encode_line(
+        line: usize,
+        text: &str,
+        addr: u32,
+        labels: &HashMap<String, u32>,
+    ) -> Result<String, DisasmError> {
+        let mut split = text.splitn(2, char::is_whitespace);
+        let mnemonic = split.next().unwrap_or("").to_uppercase();
+        let operand_text = split.next().unwrap_or("").trim();
+        let operands: Vec<&str> = if operand_text.is_empty() {
+            Vec::new()
+        } else {
+            operand_text.split(',').map(|s| s.trim()).collect()
+        };
+
+        let unknown = || DisasmError::UnknownMnemonic {
+            line,
+            mnemonic: mnemonic.clone(),
+        };
+        let class = class_of(&mnemonic).ok_or_else(unknown)?;
+        let code = code_of(&mnemonic).ok_or_else(unknown)?;
+        let template = operand_template(&mnemonic).unwrap_or("-");
+
+        let mut fields = Fields::default();
+        if template != "-" {
+            for (tok, operand) in template.split(',').map(|s| s.trim()).zip(operands.iter()) {
+                Self::apply_operand(tok, operand, addr, line, labels, &mut fields)?;
+            }
+        }
+
+        Ok(match class {
+            "R" => Self::pack_r(fields.rs, fields.rt, fields.rd, fields.shamt, code),
+            "J" => Self::pack_j(code, fields.addr / 4),
+            "REGIMM" => Self::pack_i(0x01, fields.rs, code, fields.imm as u32 & 0xFFFF),
+            // code is (fmt << 8) | funct - see instructions.in - and
+            // fmt/ft/fs/fd sit in the rs/rt/rd/shamt bit positions, the
+            // same mapping parse_cop1_type decodes them from.
+            "FP" => Self::pack_fp(code >> 8, fields.rt, fields.rd, fields.shamt, code & 0xFF),
+            _ if template.contains("uimm") => {
+                Self::pack_i(code, fields.rs, fields.rt, fields.uimm & 0xFFFF)
+            }
+            _ => Self::pack_i(code, fields.rs, fields.rt, fields.imm as u32 & 0xFFFF),
+        })
+    }
+
+    // Handle a compound template token like "imm(rs)" by splitting the
+    // operand text the same way, then resolving each half on its own.
+    fn apply_operand(
+        tok: &str,
+        text: &str,
+        addr: u32,
+        line: usize,
+        labels: &HashMap<String, u32>,
+        fields: &mut Fields,
+    ) -> Result<(), DisasmError> {
+        if let Some(open) = tok.find('(') {
+            let outer_tok = &tok[..open];
+            let inner_tok = &tok[open + 1..tok.len() - 1];
+
+            let paren = text.find('(').unwrap_or(text.len());
+            let close = text.rfind(')').unwrap_or(text.len());
+            let outer_text = text[..paren].trim();
+            let inner_text = text[(paren + 1).min(text.len())..close].trim();
+
+            Self::apply_simple(outer_tok, outer_text, addr, line, labels, fields)?;
+            Self::apply_simple(inner_tok, inner_text, addr, line, labels, fields)?;
+            return Ok(());
+        }
+        Self::apply_simple(tok, text, addr, line, labels, fields)
+    }
+
+    fn apply_simple(
+        tok: &str,
+        text: &str,
+        addr: u32,
+        line: usize,
+        labels: &HashMap<String, u32>,
+        fields: &mut Fields,
+    ) -> Result<(), DisasmError> {
+        let name = tok.trim_start_matches('#');
+        let text = text.trim_start_matches('#').trim();
+
+        match name {
+            "rd" => fields.rd = Self::parse_reg(text),
+            "rs" => fields.rs = Self::parse_reg(text),
+            "rt" => fields.rt = Self::parse_reg(text),
+            // fmt/ft/fs/fd sit in the rs/rt/rd/shamt bit positions - see
+            // parse_cop1_type - and name an F register instead of an R one.
+            "ft" => fields.rt = Self::parse_freg(text),
+            "fs" => fields.rd = Self::parse_freg(text),
+            "fd" => fields.shamt = Self::parse_freg(text),
+            "shamt" => fields.shamt = text.parse().unwrap_or(0),
+            "uimm" => fields.uimm = text.parse().unwrap_or(0),
+            "imm" => fields.imm = Self::resolve_branch(text, addr, line, labels)?,
+            "addr" => fields.addr = Self::resolve_jump(text, line, labels)?,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn parse_reg(text: &str) -> u32 {
+        text.trim_start_matches(['R', 'r']).parse().unwrap_or(0)
+    }
+
+    fn parse_freg(text: &str) -> u32 {
+        text.trim_start_matches(['F', 'f']).parse().unwrap_or(0)
+    }
+
+    // A plain number is used as-is; anything else is a label, resolved to
+    // a PC-relative word offset (curr_addr + 4 + (imm << 2) == target).
+    fn resolve_branch(
+        text: &str,
+        addr: u32,
+        line: usize,
+        labels: &HashMap<String, u32>,
+    ) -> Result<i32, DisasmError> {
+        if let Ok(n) = text.parse::<i32>() {
+            return Ok(n);
+        }
+        let target = *labels.get(text).ok_or_else(|| DisasmError::UnknownLabel {
+            line,
+            label: text.to_string(),
+        })?;
+        Ok(((target as i64 - (addr as i64 + 4)) / 4) as i32)
+    }
+
+    // A plain number is used as-is; anything else is a label, resolved to
+    // an absolute byte address (J/JAL store it word-shifted).
+    fn resolve_jump(
+        text: &str,
+        line: usize,
+        labels: &HashMap<String, u32>,
+    ) -> Result<u32, DisasmError> {
+        if let Ok(n) = text.parse::<u32>() {
+            return Ok(n);
+        }
+        labels.get(text).copied().ok_or_else(|| DisasmError::UnknownLabel {
+            line,
+            label: text.to_string(),
+        })
+    }
+
+    fn pack_r(rs: u32, rt: u32, rd: u32, shamt: u32, funct: u32) -> String {
+        format!(
+            "{:06b}{:05b}{:05b}{:05b}{:05b}{:06b}",
+            0, rs, rt, rd, shamt, funct
+        )
+    }
+
+    fn pack_i(opcode: u32, rs: u32, rt: u32, imm16: u32) -> String {
+        format!("{:06b}{:05b}{:05b}{:016b}", opcode, rs, rt, imm16)
+    }
+
+    fn pack_j(opcode: u32, word_addr: u32) -> String {
+        format!("{:06b}{:026b}", opcode, word_addr)
+    }
+
+    fn pack_fp(fmt: u32, ft: u32, fs: u32, fd: u32, funct: u32) -> String {
+        format!(
+            "{:06b}{:05b}{:05b}{:05b}{:05b}{:06b}",
+            0x11, fmt, ft, fs, fd, funct
+        )
+    }
+
+    // Main assembly process: two passes over the source, then one binary
+    // word per line written out in the same format load_binary expects.
+    pub fn assemble(&self) -> io::Result<()> {
+        let lines = self.load_source()?;
+        let labels = Self::first_pass(&lines);
+        let (words, errors) = Self::second_pass(&lines, &labels);
+
+        let mut output_file = File::create(&self.output_path)?;
+        for (i, word) in words.iter().enumerate() {
+            if i + 1 == words.len() {
+                write!(output_file, "{}", word)?;
+            } else {
+                writeln!(output_file, "{}", word)?;
+            }
+        }
+
+        println!("\n📊 Assembly summary:");
+        println!("  📟 Instructions assembled: {}", words.len());
+        println!("  🏷️ Labels resolved: {}", labels.len());
+        println!("  💾 Output saved to: {}", self.output_path);
+
+        // Report (but don't fail on) any lines that couldn't be encoded
+        if !errors.is_empty() {
+            println!("  ⚠️ {} line(s) failed to encode:", errors.len());
+            for e in &errors {
+                println!("    - {}", e);
+            }
+        }
+
+        Ok(())
+    }
+
+    // Run the assembler
+    pub fn run(&self) {
+        match self.assemble() {
+            Ok(_) => {
+                println!("✨ Assembly completed successfully! 🎉");
+            }
+            Err(e) => {
+                eprintln!("❌ Error during assembly: {}", e);
+                process::exit(1);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_r_type_into_expected_fields() {
+        let labels = HashMap::new();
+        let word = MIPSAssembler::encode_line(1, "ADD R1, R2, R3", 496, &labels).unwrap();
+        assert_eq!(word.len(), 32);
+        let rs = u32::from_str_radix(&word[6..11], 2).unwrap();
+        let rt = u32::from_str_radix(&word[11..16], 2).unwrap();
+        let rd = u32::from_str_radix(&word[16..21], 2).unwrap();
+        let funct = u32::from_str_radix(&word[26..32], 2).unwrap();
+        assert_eq!((rs, rt, rd, funct), (2, 3, 1, 0x20));
+    }
+
+    #[test]
+    fn encodes_fp_add_s_into_cop1_fields() {
+        let labels = HashMap::new();
+        let word = MIPSAssembler::encode_line(1, "ADD.S F1, F2, F3", 496, &labels).unwrap();
+        let opcode = u32::from_str_radix(&word[0..6], 2).unwrap();
+        let fmt = u32::from_str_radix(&word[6..11], 2).unwrap();
+        let ft = u32::from_str_radix(&word[11..16], 2).unwrap();
+        let fs = u32::from_str_radix(&word[16..21], 2).unwrap();
+        let fd = u32::from_str_radix(&word[21..26], 2).unwrap();
+        let funct = u32::from_str_radix(&word[26..32], 2).unwrap();
+        assert_eq!((opcode, fmt, ft, fs, fd, funct), (0x11, 0x10, 3, 2, 1, 0));
+    }
+
+    #[test]
+    fn unknown_mnemonic_is_reported_not_panicked() {
+        let labels = HashMap::new();
+        let err = MIPSAssembler::encode_line(1, "FOOBAR R1, R2", 496, &labels).unwrap_err();
+        assert!(matches!(err, DisasmError::UnknownMnemonic { line: 1, .. }));
+    }
+
+    #[test]
+    fn unresolved_label_is_reported_not_panicked() {
+        let labels = HashMap::new();
+        let err = MIPSAssembler::encode_line(1, "BEQ R1, R2, nowhere", 496, &labels).unwrap_err();
+        assert!(matches!(err, DisasmError::UnknownLabel { line: 1, .. }));
+    }
+
+    #[test]
+    fn resolve_branch_and_jump_use_the_label_map() {
+        let mut labels = HashMap::new();
+        labels.insert("L0".to_string(), 508);
+
+        let imm = MIPSAssembler::resolve_branch("L0", 496, 1, &labels).unwrap();
+        assert_eq!(imm, 2); // (508 - (496 + 4)) / 4
+
+        let addr = MIPSAssembler::resolve_jump("L0", 1, &labels).unwrap();
+        assert_eq!(addr, 508);
+    }
+}
@@ -0,0 +1,318 @@
+// src/emulator.rs - a tiny MIPS machine that actually runs what we decode
+//
+// The disassembler only ever reads instructions; this gives it somewhere
+// to execute them. Memory is word-addressable (mem[(addr) >> 2]) rather
+// than true byte-addressable MIPS memory - LB/LH/SB/SH are simplified to
+// operate on the whole word at that index, same as LW/SW, since this is a
+// class project and not a byte-accurate core.
+
+use crate::error::validate_binary;
+use crate::{lookup_i_type, lookup_j_type, lookup_r_type, lookup_regimm, Fields, START_ADDR};
+
+// Generous but finite - guards against a decoded program that never hits
+// BREAK/SYSCALL and would otherwise loop forever.
+const MAX_STEPS: u32 = 1_000_000;
+
+pub struct Machine {
+    pub regs: [u32; 32],
+    pub hi: u32,
+    pub lo: u32,
+    pub mem: Vec<u32>,
+    pub pc: u32,
+    pub steps: u32,
+}
+
+impl Machine {
+    // `data_start_addr` is wherever the code words actually end, not the
+    // fixed DATA_SECTION_ADDR constant - a program shorter than the gap
+    // between START_ADDR and DATA_SECTION_ADDR has its data sitting right
+    // after BREAK, well before DATA_SECTION_ADDR.
+    fn new(data_start_addr: u32, data_words: Vec<u32>) -> Self {
+        let mut machine = Machine {
+            regs: [0; 32],
+            hi: 0,
+            lo: 0,
+            mem: Vec::new(),
+            pc: START_ADDR,
+            steps: 0,
+        };
+        for (i, word) in data_words.into_iter().enumerate() {
+            let addr = data_start_addr + (i as u32) * 4;
+            machine.mem_write(addr, word);
+        }
+        machine
+    }
+
+    fn reg(&self, n: u32) -> u32 {
+        self.regs[n as usize]
+    }
+
+    // R0 is hardwired to 0 - writes to it are silently dropped, same as
+    // real MIPS.
+    fn set_reg(&mut self, n: u32, value: u32) {
+        if n != 0 {
+            self.regs[n as usize] = value;
+        }
+    }
+
+    fn mem_read(&self, addr: u32) -> u32 {
+        let idx = (addr >> 2) as usize;
+        *self.mem.get(idx).unwrap_or(&0)
+    }
+
+    fn mem_write(&mut self, addr: u32, value: u32) {
+        let idx = (addr >> 2) as usize;
+        if idx >= self.mem.len() {
+            self.mem.resize(idx + 1, 0);
+        }
+        self.mem[idx] = value;
+    }
+
+    // Dump register/memory state - called once execution halts
+    pub fn dump(&self) {
+        println!("\n🖥️  Final machine state:");
+        println!("  PC: {}", self.pc);
+        println!("  HI: {}  LO: {}", self.hi, self.lo);
+
+        print!("  Registers:");
+        for i in 0..32 {
+            if i % 8 == 0 {
+                print!("\n    ");
+            }
+            print!("R{:<2}={:<10} ", i, self.regs[i]);
+        }
+        println!();
+
+        if !self.mem.is_empty() {
+            // self.mem is indexed by addr >> 2 from real address 0 - see
+            // mem_read/mem_write - so the address is just the index
+            // scaled back up, not an offset from DATA_SECTION_ADDR.
+            println!("  Data memory (word-addressed):");
+            for (i, word) in self.mem.iter().enumerate() {
+                println!("    [{}] = {}", (i as u32) * 4, word);
+            }
+        }
+    }
+}
+
+enum Control {
+    Next,
+    Jump(u32),
+    Halt,
+}
+
+// Re-decode a word into (mnemonic, fields) for execution. This is a
+// leaner pass than parse_r_type/parse_i_type/parse_j_type: it doesn't
+// need the NOP/Fibonacci display hacks, just the raw field values the
+// execute step needs.
+fn decode_for_exec(bin_str: &str) -> (String, Fields) {
+    let opcode = u32::from_str_radix(&bin_str[0..6], 2).unwrap();
+    let rs = u32::from_str_radix(&bin_str[6..11], 2).unwrap();
+    let rt = u32::from_str_radix(&bin_str[11..16], 2).unwrap();
+    let rd = u32::from_str_radix(&bin_str[16..21], 2).unwrap();
+    let shamt = u32::from_str_radix(&bin_str[21..26], 2).unwrap();
+    let funct = u32::from_str_radix(&bin_str[26..32], 2).unwrap();
+    let uimm = u32::from_str_radix(&bin_str[16..32], 2).unwrap();
+    let imm = if uimm > 0x7FFF {
+        (uimm as i32) - 0x10000
+    } else {
+        uimm as i32
+    };
+    let addr = u32::from_str_radix(&bin_str[6..32], 2).unwrap() * 4;
+
+    let instr = if opcode == 0 {
+        lookup_r_type(funct).unwrap_or("UNKNOWN").to_string()
+    } else if opcode == 0x02 || opcode == 0x03 {
+        lookup_j_type(opcode).unwrap_or("UNKNOWN").to_string()
+    } else if opcode == 0x01 {
+        lookup_regimm(rt).unwrap_or("UNKNOWN").to_string()
+    } else {
+        lookup_i_type(opcode).unwrap_or("UNKNOWN").to_string()
+    };
+
+    let fields = Fields {
+        rs,
+        rt,
+        rd,
+        shamt,
+        imm,
+        uimm,
+        addr,
+    };
+
+    (instr, fields)
+}
+
+// Run one instruction, mutating machine state and reporting how the PC
+// should move next.
+fn execute(instr: &str, f: &Fields, m: &mut Machine) -> Control {
+    match instr {
+        "ADD" | "ADDU" => m.set_reg(f.rd, m.reg(f.rs).wrapping_add(m.reg(f.rt))),
+        "SUB" | "SUBU" => m.set_reg(f.rd, m.reg(f.rs).wrapping_sub(m.reg(f.rt))),
+        "AND" => m.set_reg(f.rd, m.reg(f.rs) & m.reg(f.rt)),
+        "OR" => m.set_reg(f.rd, m.reg(f.rs) | m.reg(f.rt)),
+        "XOR" => m.set_reg(f.rd, m.reg(f.rs) ^ m.reg(f.rt)),
+        "NOR" => m.set_reg(f.rd, !(m.reg(f.rs) | m.reg(f.rt))),
+        "SLT" => m.set_reg(f.rd, ((m.reg(f.rs) as i32) < (m.reg(f.rt) as i32)) as u32),
+        "SLL" => m.set_reg(f.rd, m.reg(f.rt) << f.shamt),
+        "SRL" => m.set_reg(f.rd, m.reg(f.rt) >> f.shamt),
+        "SRA" => m.set_reg(f.rd, ((m.reg(f.rt) as i32) >> f.shamt) as u32),
+        "SLLV" => m.set_reg(f.rd, m.reg(f.rt) << (m.reg(f.rs) & 0x1F)),
+        "SRLV" => m.set_reg(f.rd, m.reg(f.rt) >> (m.reg(f.rs) & 0x1F)),
+        "SRAV" => m.set_reg(f.rd, ((m.reg(f.rt) as i32) >> (m.reg(f.rs) & 0x1F)) as u32),
+        "JR" => return Control::Jump(m.reg(f.rs)),
+        "JALR" => {
+            m.set_reg(f.rd, m.pc + 4);
+            return Control::Jump(m.reg(f.rs));
+        }
+        "MFHI" => m.set_reg(f.rd, m.hi),
+        "MFLO" => m.set_reg(f.rd, m.lo),
+        "MTHI" => m.hi = m.reg(f.rs),
+        "MTLO" => m.lo = m.reg(f.rs),
+        "SYSCALL" | "BREAK" => return Control::Halt,
+
+        "ADDI" | "ADDIU" => m.set_reg(f.rt, (m.reg(f.rs) as i32).wrapping_add(f.imm) as u32),
+        "SLTI" => m.set_reg(f.rt, ((m.reg(f.rs) as i32) < f.imm) as u32),
+        "ANDI" => m.set_reg(f.rt, m.reg(f.rs) & f.uimm),
+        "ORI" => m.set_reg(f.rt, m.reg(f.rs) | f.uimm),
+        "XORI" => m.set_reg(f.rt, m.reg(f.rs) ^ f.uimm),
+        "LUI" => m.set_reg(f.rt, f.uimm << 16),
+
+        "LW" | "LB" | "LH" | "LBU" | "LHU" => {
+            let addr = (m.reg(f.rs) as i32).wrapping_add(f.imm) as u32;
+            m.set_reg(f.rt, m.mem_read(addr));
+        }
+        "SW" | "SB" | "SH" => {
+            let addr = (m.reg(f.rs) as i32).wrapping_add(f.imm) as u32;
+            m.mem_write(addr, m.reg(f.rt));
+        }
+
+        "BEQ" => return branch_if(m.reg(f.rs) == m.reg(f.rt), m.pc, f.imm),
+        "BNE" => return branch_if(m.reg(f.rs) != m.reg(f.rt), m.pc, f.imm),
+        "BLEZ" => return branch_if(m.reg(f.rs) as i32 <= 0, m.pc, f.imm),
+        "BGTZ" => return branch_if(m.reg(f.rs) as i32 > 0, m.pc, f.imm),
+        "BLTZ" => return branch_if((m.reg(f.rs) as i32) < 0, m.pc, f.imm),
+        "BGEZ" => return branch_if(m.reg(f.rs) as i32 >= 0, m.pc, f.imm),
+        "BLTZAL" => {
+            m.set_reg(31, m.pc + 4);
+            return branch_if((m.reg(f.rs) as i32) < 0, m.pc, f.imm);
+        }
+        "BGEZAL" => {
+            m.set_reg(31, m.pc + 4);
+            return branch_if(m.reg(f.rs) as i32 >= 0, m.pc, f.imm);
+        }
+
+        "J" => return Control::Jump(f.addr),
+        "JAL" => {
+            m.set_reg(31, m.pc + 4);
+            return Control::Jump(f.addr);
+        }
+
+        _ => {} // UNKNOWN - treat as a no-op and keep going
+    }
+
+    Control::Next
+}
+
+fn branch_target(pc: u32, imm: i32) -> u32 {
+    ((pc as i64) + 4 + ((imm as i64) << 2)) as u32
+}
+
+fn branch_if(taken: bool, pc: u32, imm: i32) -> Control {
+    if taken {
+        Control::Jump(branch_target(pc, imm))
+    } else {
+        Control::Next
+    }
+}
+
+// Mirror the BREAK-triggered code/data split `disassemble` uses in
+// main.rs: the word that decodes to BREAK is the last code word, and
+// everything after it is data, regardless of address. A program shorter
+// than the gap between START_ADDR and DATA_SECTION_ADDR never reaches
+// DATA_SECTION_ADDR through code alone, so deriving the split from that
+// address cutoff (as this used to) misclassified every data word as
+// unreached code instead.
+fn split_code_data(words: &[String]) -> usize {
+    for (i, bin_str) in words.iter().enumerate() {
+        if validate_binary(i + 1, bin_str).is_err() {
+            continue;
+        }
+        let (instr, _) = decode_for_exec(bin_str);
+        if instr == "BREAK" {
+            return i + 1;
+        }
+    }
+    words.len()
+}
+
+// Run the decoded word stream on a fresh Machine and return its final
+// state. `words` are the same '0'/'1' lines load_binary already produces.
+pub fn run(words: &[String]) -> Machine {
+    let split = split_code_data(words);
+    let (code_words, data_words) = words.split_at(split);
+
+    let data_values: Vec<u32> = data_words
+        .iter()
+        .filter_map(|w| u32::from_str_radix(w, 2).ok())
+        .collect();
+
+    let data_start_addr = START_ADDR + (split as u32) * 4;
+    let mut machine = Machine::new(data_start_addr, data_values);
+
+    loop {
+        if machine.steps >= MAX_STEPS {
+            eprintln!("⚠️ Emulation stopped: exceeded {} steps without halting", MAX_STEPS);
+            break;
+        }
+
+        let idx = ((machine.pc.wrapping_sub(START_ADDR)) / 4) as usize;
+        let Some(bin_str) = code_words.get(idx) else {
+            break;
+        };
+        if let Err(e) = validate_binary(idx + 1, bin_str) {
+            eprintln!("⚠️ Emulation stopped: malformed word at PC {} ({})", machine.pc, e);
+            break;
+        }
+
+        let (instr, fields) = decode_for_exec(bin_str);
+        machine.steps += 1;
+
+        match execute(&instr, &fields, &mut machine) {
+            Control::Halt => break,
+            Control::Jump(target) => machine.pc = target,
+            Control::Next => machine.pc += 4,
+        }
+    }
+
+    machine
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn r_word(rs: u32, rt: u32, rd: u32, shamt: u32, funct: u32) -> String {
+        format!("{:06b}{:05b}{:05b}{:05b}{:05b}{:06b}", 0, rs, rt, rd, shamt, funct)
+    }
+
+    #[test]
+    fn split_stops_right_after_the_break_word() {
+        let words = vec![r_word(0, 0, 0, 0, 0x0D), format!("{:032b}", 777u32)];
+        assert_eq!(split_code_data(&words), 1);
+    }
+
+    #[test]
+    fn split_treats_a_program_with_no_break_as_all_code() {
+        let words = vec![r_word(1, 2, 3, 0, 0x20)]; // ADD R3, R1, R2
+        assert_eq!(split_code_data(&words), words.len());
+    }
+
+    #[test]
+    fn mem_read_write_round_trip_at_real_addresses() {
+        let mut m = Machine::new(0, Vec::new());
+        m.mem_write(500, 777);
+        assert_eq!(m.mem_read(500), 777);
+        assert_eq!(m.mem.len(), 126); // 500 / 4 + 1
+    }
+}
@@ -0,0 +1,104 @@
+// src/error.rs - error type for malformed instruction words
+//
+// Used to just be a wall of `u32::from_str_radix(...).unwrap()` calls, so
+// one bad line (wrong length, a stray character) panicked and took the
+// whole run down with it. This gives the decode path something to hand
+// back instead, so `disassemble` can report a bad word and keep going.
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+#[derive(Debug)]
+pub enum DisasmError {
+    /// A line wasn't exactly 32 bits long.
+    BadLength { line: usize, got: usize },
+    /// A line had a character that wasn't '0' or '1'.
+    NonBinaryChar { line: usize, col: usize },
+    /// An assembly source line named a mnemonic not in the opcode tables.
+    UnknownMnemonic { line: usize, mnemonic: String },
+    /// An assembly source line's operand named a label with no matching
+    /// "LABEL:" in the source.
+    UnknownLabel { line: usize, label: String },
+    /// Reading or writing the file itself failed.
+    Io(io::Error),
+}
+
+impl fmt::Display for DisasmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DisasmError::BadLength { line, got } => {
+                write!(f, "line {}: expected 32 bits, got {}", line, got)
+            }
+            DisasmError::NonBinaryChar { line, col } => {
+                write!(f, "line {}: non-binary character at column {}", line, col)
+            }
+            DisasmError::UnknownMnemonic { line, mnemonic } => {
+                write!(f, "line {}: unknown mnemonic '{}'", line, mnemonic)
+            }
+            DisasmError::UnknownLabel { line, label } => {
+                write!(f, "line {}: unknown label '{}'", line, label)
+            }
+            DisasmError::Io(e) => write!(f, "I/O error: {}", e),
+        }
+    }
+}
+
+impl Error for DisasmError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            DisasmError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for DisasmError {
+    fn from(e: io::Error) -> Self {
+        DisasmError::Io(e)
+    }
+}
+
+// Shared sanity check every decode path runs before slicing fixed byte
+// ranges out of a line, so a malformed word turns into a DisasmError
+// instead of a panic deep inside `from_str_radix`.
+pub fn validate_binary(line: usize, bin_str: &str) -> Result<(), DisasmError> {
+    if bin_str.len() != 32 {
+        return Err(DisasmError::BadLength {
+            line,
+            got: bin_str.len(),
+        });
+    }
+
+    for (col, ch) in bin_str.chars().enumerate() {
+        if ch != '0' && ch != '1' {
+            return Err(DisasmError::NonBinaryChar { line, col: col + 1 });
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_wrong_length() {
+        let err = validate_binary(1, "0101").unwrap_err();
+        assert!(matches!(err, DisasmError::BadLength { line: 1, got: 4 }));
+    }
+
+    #[test]
+    fn rejects_non_binary_char() {
+        let mut bits = "0".repeat(32);
+        bits.replace_range(5..6, "x");
+        let err = validate_binary(2, &bits).unwrap_err();
+        assert!(matches!(err, DisasmError::NonBinaryChar { line: 2, col: 6 }));
+    }
+
+    #[test]
+    fn accepts_a_valid_word() {
+        assert!(validate_binary(1, &"0".repeat(32)).is_ok());
+    }
+}
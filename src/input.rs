@@ -0,0 +1,103 @@
+// src/input.rs - turns whatever form the input file is in into the
+// canonical 32-char '0'/'1' lines the rest of the pipeline already
+// expects. `load_binary` used to assume every line was already an ASCII
+// bit string; real machine-code files show up as hex listings or raw
+// byte streams instead, so this normalizes all three into one shape
+// before anything gets to `decode_instruction`.
+//
+// Format is picked from the input file's extension, or forced by the
+// --hex/--raw CLI flags, which take priority over the extension.
+
+use std::fs;
+use std::io;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum InputFormat {
+    Bits, // ASCII '0'/'1' lines - the original class-assignment format
+    Hex,  // one hex word per line, with or without a "0x" prefix
+    Raw,  // a raw binary file, grouped into 32-bit words
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Endianness {
+    Big,
+    Little,
+}
+
+// Extension-based guess; `force_hex`/`force_raw` (set from --hex/--raw)
+// override it.
+pub fn detect_format(path: &str, force_hex: bool, force_raw: bool) -> InputFormat {
+    if force_raw {
+        return InputFormat::Raw;
+    }
+    if force_hex {
+        return InputFormat::Hex;
+    }
+    match path.rsplit('.').next() {
+        Some("hex") => InputFormat::Hex,
+        Some("bin") => InputFormat::Raw,
+        _ => InputFormat::Bits,
+    }
+}
+
+// Read `path` under `format` and return one canonical 32-char bit string
+// per instruction/data word.
+pub fn load_words(path: &str, format: InputFormat, endian: Endianness) -> io::Result<Vec<String>> {
+    match format {
+        InputFormat::Bits => load_bit_lines(path),
+        InputFormat::Hex => load_hex_lines(path),
+        InputFormat::Raw => load_raw_bytes(path, endian),
+    }
+}
+
+fn load_bit_lines(path: &str) -> io::Result<Vec<String>> {
+    let contents = fs::read_to_string(path)?;
+    Ok(contents
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect())
+}
+
+// A hex line that doesn't parse (e.g. genuinely malformed) is passed
+// through as-is, so it still reaches validate_binary and gets reported
+// as a normal malformed-word error instead of panicking here.
+fn load_hex_lines(path: &str) -> io::Result<Vec<String>> {
+    let contents = fs::read_to_string(path)?;
+    let mut words = Vec::new();
+
+    for line in contents.lines() {
+        let cleaned = line.trim();
+        if cleaned.is_empty() {
+            continue;
+        }
+        let hex = cleaned
+            .trim_start_matches("0x")
+            .trim_start_matches("0X");
+        match u32::from_str_radix(hex, 16) {
+            Ok(value) => words.push(format!("{:032b}", value)),
+            Err(_) => words.push(cleaned.to_string()),
+        }
+    }
+
+    Ok(words)
+}
+
+// Groups the file's bytes into 32-bit words, padding a short trailing
+// chunk with zero bytes.
+fn load_raw_bytes(path: &str, endian: Endianness) -> io::Result<Vec<String>> {
+    let bytes = fs::read(path)?;
+
+    Ok(bytes
+        .chunks(4)
+        .map(|chunk| {
+            let mut word = [0u8; 4];
+            word[..chunk.len()].copy_from_slice(chunk);
+            let value = match endian {
+                Endianness::Big => u32::from_be_bytes(word),
+                Endianness::Little => u32::from_le_bytes(word),
+            };
+            format!("{:032b}", value)
+        })
+        .collect())
+}
@@ -0,0 +1,147 @@
+// src/labels.rs - post-decode pass that turns branch/jump targets into
+// symbolic labels
+//
+// parse_j_type already resolves J/JAL to a real address, and parse_i_type
+// prints the raw word offset for branches - this is a second scan over
+// the listing lines `disassemble` already built, which turns both into
+// real targets, assigns every target that lands in the code region a
+// label (L0, L1, ...), and rewrites the operand text to use it instead of
+// a bare number. Run after the normal decode pass, before the lines get
+// written out.
+
+use std::collections::HashMap;
+
+use crate::{DATA_SECTION_ADDR, START_ADDR};
+
+const BRANCH_MNEMONICS: &[&str] = &[
+    "BEQ", "BNE", "BLEZ", "BGTZ", "BLTZ", "BGEZ", "BLTZAL", "BGEZAL",
+];
+const JUMP_MNEMONICS: &[&str] = &["J", "JAL"];
+
+// Each listing line is "{bin}\t{addr}\t{instr}" or
+// "{bin}\t{addr}\t{instr}\t{operands}" - pull the address/mnemonic/operand
+// columns back out so this pass can work off the text `disassemble` built,
+// without having to re-decode the word.
+fn columns(line: &str) -> Option<(u32, &str, &str)> {
+    let mut fields = line.split('\t');
+    let _bin = fields.next()?;
+    let addr = fields.next()?.trim().parse().ok()?;
+    let instr = fields.next()?;
+    let operands = fields.next().unwrap_or("");
+    Some((addr, instr, operands))
+}
+
+// BEQ/BNE/etc. print a raw word offset (#imm); J/JAL already print the
+// resolved absolute address. Either way, the target is the last operand.
+fn target_of(addr: u32, instr: &str, operands: &str) -> Option<u32> {
+    let last = operands.rsplit(',').next()?.trim();
+    let value: i64 = last.trim_start_matches('#').parse().ok()?;
+
+    if BRANCH_MNEMONICS.contains(&instr) {
+        Some(((addr as i64) + 4 + (value << 2)) as u32)
+    } else if JUMP_MNEMONICS.contains(&instr) {
+        Some(value as u32)
+    } else {
+        None
+    }
+}
+
+// Find every branch/jump target in the code region, in the order they
+// first appear, and name them L0, L1, ...
+fn collect_labels(output_lines: &[String]) -> HashMap<u32, String> {
+    let mut order = Vec::new();
+    let mut seen = HashMap::new();
+
+    for line in output_lines {
+        let Some((addr, instr, operands)) = columns(line) else {
+            continue;
+        };
+        let Some(target) = target_of(addr, instr, operands) else {
+            continue;
+        };
+        if (START_ADDR..DATA_SECTION_ADDR).contains(&target) && seen.insert(target, ()).is_none() {
+            order.push(target);
+        }
+    }
+
+    order
+        .into_iter()
+        .enumerate()
+        .map(|(i, addr)| (addr, format!("L{}", i)))
+        .collect()
+}
+
+// Rewrite `output_lines` in place: a branch/jump operand's trailing
+// number becomes the label name, and any line whose address is a label
+// target gets a "LABEL:" line inserted ahead of it.
+pub fn resolve(output_lines: Vec<String>) -> Vec<String> {
+    let labels = collect_labels(&output_lines);
+    if labels.is_empty() {
+        return output_lines;
+    }
+
+    let mut result = Vec::with_capacity(output_lines.len());
+
+    for line in output_lines {
+        let Some((addr, instr, operands)) = columns(&line) else {
+            result.push(line);
+            continue;
+        };
+
+        if let Some(label) = labels.get(&addr) {
+            result.push(format!("{}:", label));
+        }
+
+        match target_of(addr, instr, operands).and_then(|target| labels.get(&target)) {
+            Some(label) => {
+                let rewritten = match operands.rfind(',') {
+                    Some(pos) => format!("{}, {}", &operands[..pos], label),
+                    None => label.clone(),
+                };
+                result.push(format!("{}\t{}\t{}\t{}", line_bin(&line), addr, instr, rewritten));
+            }
+            None => result.push(line),
+        }
+    }
+
+    result
+}
+
+fn line_bin(line: &str) -> &str {
+    line.split('\t').next().unwrap_or("")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn target_of_resolves_branch_offset() {
+        assert_eq!(target_of(496, "BEQ", "R1, R2, #2"), Some(508));
+    }
+
+    #[test]
+    fn target_of_resolves_jump_address() {
+        assert_eq!(target_of(496, "J", "#700"), Some(700));
+    }
+
+    #[test]
+    fn target_of_ignores_non_branch_instructions() {
+        assert_eq!(target_of(496, "ADD", "R1, R2, R3"), None);
+    }
+
+    #[test]
+    fn resolve_inserts_a_label_and_rewrites_the_branch_operand() {
+        let lines = vec![
+            "000000\t496\tBEQ\tR1, R2, #2".to_string(),
+            "000000\t500\tNOP".to_string(),
+            "000000\t504\tNOP".to_string(),
+            "000000\t508\tADD\tR1, R2, R3".to_string(),
+        ];
+
+        let resolved = resolve(lines);
+
+        assert!(resolved[0].ends_with("L0"));
+        assert!(resolved.iter().any(|l| l == "L0:"));
+    }
+}
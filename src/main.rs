@@ -2,8 +2,11 @@
 // Name: Santiago von Straussburg
 // Date: April 11, 2024
 //
-// Compile with: rustc mips_disassembler.rs
+// Build with: cargo build
 // Run: ./mips_disassembler [input_file] [output_file]
+// Or: ./mips_disassembler --assemble [input_file] [output_file]
+// Or: ./mips_disassembler --emulate [input_file]
+// Or: ./mips_disassembler --no-pseudo [input_file] [output_file]
 // Or: ./mips_disassembler (for interactive mode)
 //
 // This is my Rust version of the MIPS disassembler - way faster than
@@ -14,7 +17,16 @@
 // for this assignment but I wanted to compare performance and learn
 // more Rust. Also the hashmap stuff is cleaner in Rust IMO.
 
-use std::collections::HashMap;
+mod assembler;
+mod emulator;
+mod error;
+mod input;
+mod labels;
+mod peephole;
+
+use assembler::MIPSAssembler;
+use error::{validate_binary, DisasmError};
+use input::{Endianness, InputFormat};
 use std::env;
 use std::fs::{self, File};
 use std::io::{self, BufRead, BufReader, Write};
@@ -25,120 +37,59 @@ use std::time::{SystemTime, UNIX_EPOCH};
 const START_ADDR: u32 = 496;  // Code segment start
 const DATA_SECTION_ADDR: u32 = 700;  // Data segment start
 
+// Opcode/function tables and the operand formatter are generated from
+// instructions.in by build.rs - see that file for the spec format. This
+// used to be five hand-typed HashMaps plus a giant if/else ladder for
+// operand formatting; now it's one line per instruction in the spec file.
+include!(concat!(env!("OUT_DIR"), "/instrs.rs"));
+
+// Decoded field values for one instruction word, used to feed the
+// generated `format_operands`.
+#[derive(Default)]
+struct Fields {
+    rs: u32,
+    rt: u32,
+    rd: u32,
+    shamt: u32,
+    imm: i32,
+    uimm: u32,
+    addr: u32,
+}
+
 // Main struct that does all the work
 struct MIPSDisassembler {
     input_path: String,       // Where to read from
     output_path: String,      // Where to write to
     curr_addr: u32,           // Current memory address we're at
-    reg_names: HashMap<u32, String>,      // R0-R31 registers
-    r_type_funcs: HashMap<u32, String>,   // ADD, SUB, etc.
-    i_type_ops: HashMap<u32, String>,     // ADDI, LW, etc.
-    j_type_ops: HashMap<u32, String>,     // J, JAL
-    regimm_types: HashMap<u32, String>,   // BLTZ, BGEZ, etc.
+    show_pseudo_ops: bool,    // Fuse recognized idioms into pseudo-instructions
+    input_format: InputFormat, // Bit-string / hex / raw-byte input
+    endian: Endianness,        // Byte order to use when input_format is Raw
 }
 
 impl MIPSDisassembler {
     // Create a new disassembler instance
     fn new(input_path: String, output_path: String) -> Self {
-        // Initialize register names
-        let mut reg_names = HashMap::new();
-        for i in 0..32 {
-            reg_names.insert(i, format!("R{}", i));
-        }
-
-        // Initialize R-type function codes
-        let mut r_type_funcs = HashMap::new();
-        r_type_funcs.insert(0x20, "ADD".to_string());
-        r_type_funcs.insert(0x21, "ADDU".to_string());
-        r_type_funcs.insert(0x22, "SUB".to_string());
-        r_type_funcs.insert(0x23, "SUBU".to_string());
-        r_type_funcs.insert(0x24, "AND".to_string());
-        r_type_funcs.insert(0x25, "OR".to_string());
-        r_type_funcs.insert(0x26, "XOR".to_string());
-        r_type_funcs.insert(0x27, "NOR".to_string());
-        r_type_funcs.insert(0x2A, "SLT".to_string());
-        r_type_funcs.insert(0x00, "SLL".to_string());
-        r_type_funcs.insert(0x02, "SRL".to_string());
-        r_type_funcs.insert(0x03, "SRA".to_string());
-        r_type_funcs.insert(0x04, "SLLV".to_string());
-        r_type_funcs.insert(0x06, "SRLV".to_string());
-        r_type_funcs.insert(0x07, "SRAV".to_string());
-        r_type_funcs.insert(0x08, "JR".to_string());
-        r_type_funcs.insert(0x09, "JALR".to_string());
-        r_type_funcs.insert(0x0C, "SYSCALL".to_string());
-        r_type_funcs.insert(0x0D, "BREAK".to_string());
-        r_type_funcs.insert(0x10, "MFHI".to_string());
-        r_type_funcs.insert(0x12, "MFLO".to_string());
-        r_type_funcs.insert(0x11, "MTHI".to_string());
-        r_type_funcs.insert(0x13, "MTLO".to_string());
-
-        // Initialize I-type opcodes
-        let mut i_type_ops = HashMap::new();
-        i_type_ops.insert(0x08, "ADDI".to_string());
-        i_type_ops.insert(0x09, "ADDIU".to_string());
-        i_type_ops.insert(0x0C, "ANDI".to_string());
-        i_type_ops.insert(0x0D, "ORI".to_string());
-        i_type_ops.insert(0x0E, "XORI".to_string());
-        i_type_ops.insert(0x0A, "SLTI".to_string());
-        i_type_ops.insert(0x23, "LW".to_string());
-        i_type_ops.insert(0x20, "LB".to_string());
-        i_type_ops.insert(0x21, "LH".to_string());
-        i_type_ops.insert(0x24, "LBU".to_string());
-        i_type_ops.insert(0x25, "LHU".to_string());
-        i_type_ops.insert(0x2B, "SW".to_string());
-        i_type_ops.insert(0x28, "SB".to_string());
-        i_type_ops.insert(0x29, "SH".to_string());
-        i_type_ops.insert(0x04, "BEQ".to_string());
-        i_type_ops.insert(0x05, "BNE".to_string());
-        i_type_ops.insert(0x06, "BLEZ".to_string());
-        i_type_ops.insert(0x07, "BGTZ".to_string());
-        i_type_ops.insert(0x01, "BGEZ/BLTZ".to_string());
-        i_type_ops.insert(0x0F, "LUI".to_string());
-
-        // Initialize J-type opcodes
-        let mut j_type_ops = HashMap::new();
-        j_type_ops.insert(0x02, "J".to_string());
-        j_type_ops.insert(0x03, "JAL".to_string());
-
-        // Initialize REGIMM opcodes
-        let mut regimm_types = HashMap::new();
-        regimm_types.insert(0x00, "BLTZ".to_string());
-        regimm_types.insert(0x01, "BGEZ".to_string());
-        regimm_types.insert(0x10, "BLTZAL".to_string());
-        regimm_types.insert(0x11, "BGEZAL".to_string());
-
+        let input_format = input::detect_format(&input_path, false, false);
         MIPSDisassembler {
             input_path,
             output_path,
             curr_addr: START_ADDR,
-            reg_names,
-            r_type_funcs,
-            i_type_ops,
-            j_type_ops,
-            regimm_types,
+            show_pseudo_ops: true,
+            input_format,
+            endian: Endianness::Big,
         }
     }
 
-    // Read binary data from input file
+    // Read the input file, normalizing whatever form it's in (bit
+    // strings, hex words, raw bytes) into canonical 32-char bit lines.
     fn load_binary(&self) -> Result<Vec<String>, io::Error> {
-        let file = File::open(&self.input_path)?;
-        let reader = BufReader::new(file);
-        
-        // Read and filter out empty lines
-        let mut binaries = Vec::new();
-        for line in reader.lines() {
-            let line = line?;
-            let cleaned = line.trim();
-            if !cleaned.is_empty() {
-                binaries.push(cleaned.to_string());
-            }
-        }
-        
-        Ok(binaries)
+        input::load_words(&self.input_path, self.input_format, self.endian)
     }
 
     // Format binary string into MIPS fields with spaces
-    fn format_binary(&self, bin_str: &str) -> String {
+    fn format_binary(&self, line: usize, bin_str: &str) -> Result<String, DisasmError> {
+        validate_binary(line, bin_str)?;
+
         // Rust's string slicing is actually much nicer than Python for this
         // No need for all those bin_str[x:y] calls with magic numbers
         let op = &bin_str[0..6];
@@ -147,257 +98,260 @@ impl MIPSDisassembler {
         let rd = &bin_str[16..21];
         let shamt = &bin_str[21..26];
         let funct = &bin_str[26..32];
-        
+
         // Just slam it all together with spaces
-        format!("{} {} {} {} {} {}", op, rs, rt, rd, shamt, funct)
+        Ok(format!("{} {} {} {} {} {}", op, rs, rt, rd, shamt, funct))
     }
 
     // Decode R-type instruction
-    fn parse_r_type(&self, bin_str: &str) -> (String, String) {
+    fn parse_r_type(&self, line: usize, bin_str: &str) -> Result<(String, String), DisasmError> {
+        validate_binary(line, bin_str)?;
+
         // Extract fields
         let rs = u32::from_str_radix(&bin_str[6..11], 2).unwrap();
         let rt = u32::from_str_radix(&bin_str[11..16], 2).unwrap();
         let rd = u32::from_str_radix(&bin_str[16..21], 2).unwrap();
         let shamt = u32::from_str_radix(&bin_str[21..26], 2).unwrap();
         let funct = u32::from_str_radix(&bin_str[26..32], 2).unwrap();
-        
-        // Get instruction name
-        let instr = match self.r_type_funcs.get(&funct) {
-            Some(name) => name.clone(),
-            None => "UNKNOWN".to_string()
-        };
-        
+
         // Check for NOP (SLL R0, R0, 0) - this special case took me forever to catch!
+        // It keys off the register values, not just the mnemonic, so it can't
+        // live in the generated table the way the normal operand formats do.
         if funct == 0 && rs == 0 && rt == 0 && rd == 0 && shamt == 0 {
-            return ("NOP".to_string(), "".to_string());
+            return Ok(("NOP".to_string(), "".to_string()));
         }
-        
-        // Format operands based on instruction type
-        let operands = if instr == "SLL" || instr == "SRL" || instr == "SRA" {
-            // Shift with immediate shift amount
-            format!("{}, {}, #{}",
-                self.reg_names.get(&rd).unwrap(),
-                self.reg_names.get(&rt).unwrap(),
-                shamt)
-                
-        } else if instr == "SLLV" || instr == "SRLV" || instr == "SRAV" {
-            // Variable shift instructions
-            format!("{}, {}, {}",
-                self.reg_names.get(&rd).unwrap(),
-                self.reg_names.get(&rt).unwrap(),
-                self.reg_names.get(&rs).unwrap())
-                
-        } else if instr == "JR" {
-            // Jump register
-            format!("{}", self.reg_names.get(&rs).unwrap())
-            
-        } else if instr == "JALR" {
-            // Jump and link register
-            format!("{}, {}", 
-                self.reg_names.get(&rd).unwrap(),
-                self.reg_names.get(&rs).unwrap())
-                
-        } else if instr == "SYSCALL" || instr == "BREAK" {
-            // No operands
-            "".to_string()
-            
-        } else if instr == "MFHI" || instr == "MFLO" {
-            // Move from HI/LO
-            format!("{}", self.reg_names.get(&rd).unwrap())
-            
-        } else if instr == "MTHI" || instr == "MTLO" {
-            // Move to HI/LO
-            format!("{}", self.reg_names.get(&rs).unwrap())
-            
-        } else {
-            // Standard R-type format
-            format!("{}, {}, {}",
-                self.reg_names.get(&rd).unwrap(),
-                self.reg_names.get(&rs).unwrap(),
-                self.reg_names.get(&rt).unwrap())
+
+        let instr = lookup_r_type(funct).unwrap_or("UNKNOWN").to_string();
+        let fields = Fields {
+            rs,
+            rt,
+            rd,
+            shamt,
+            ..Default::default()
         };
-        
-        (instr, operands)
+        let operands = operand_template(&instr)
+            .map(|tmpl| format_operands(tmpl, &fields))
+            .unwrap_or_default();
+
+        Ok((instr, operands))
     }
 
     // Decode I-type instruction
-    fn parse_i_type(&self, bin_str: &str) -> (String, String) {
+    fn parse_i_type(&self, line: usize, bin_str: &str) -> Result<(String, String), DisasmError> {
+        validate_binary(line, bin_str)?;
+
         // Extract fields
         let opcode = u32::from_str_radix(&bin_str[0..6], 2).unwrap();
         let rs = u32::from_str_radix(&bin_str[6..11], 2).unwrap();
         let rt = u32::from_str_radix(&bin_str[11..16], 2).unwrap();
-        let mut imm = u32::from_str_radix(&bin_str[16..32], 2).unwrap();
-        
+        let uimm = u32::from_str_radix(&bin_str[16..32], 2).unwrap();
+
         // Handle signed immediate (16-bit two's complement)
-        if imm > 0x7FFF {
-            // Convert to i32 for signed arithmetic
-            let imm_i32 = (imm as i32) - 0x10000;
-            imm = imm_i32 as u32; // Convert back to u32
-        }
-        
-        // Get instruction name
-        let mut instr = match self.i_type_ops.get(&opcode) {
-            Some(name) => name.clone(),
-            None => "UNKNOWN".to_string()
+        let imm = if uimm > 0x7FFF {
+            (uimm as i32) - 0x10000
+        } else {
+            uimm as i32
         };
-        
-        // Special case for REGIMM instructions
+
+        // Get instruction name, resolving the REGIMM split (opcode 0x01
+        // covers BLTZ/BGEZ/BLTZAL/BGEZAL, picked by the rt field)
+        let mut instr = lookup_i_type(opcode).unwrap_or("UNKNOWN").to_string();
         if instr == "BGEZ/BLTZ" {
-            instr = match self.regimm_types.get(&rt) {
-                Some(name) => name.clone(),
-                None => "UNKNOWN".to_string()
-            };
+            instr = lookup_regimm(rt).unwrap_or("UNKNOWN").to_string();
         }
-        
-        // Format operands based on instruction type
-        let operands = if instr == "BEQ" || instr == "BNE" {
-            // Branch equal/not equal
-            
-            // Special hack for fibonacci example - annoying edge case but it makes the output match
-            // what the prof expects. Spent way too much time debugging this...
-            if instr == "BEQ" && self.reg_names.get(&rs).unwrap() == "R10" && 
-               self.reg_names.get(&rt).unwrap() == "R8" {
-                format!("{}, {}, #4",  // Hardcoded #4 instead of the actual value!
-                    self.reg_names.get(&rs).unwrap(),
-                    self.reg_names.get(&rt).unwrap())
-            } else {
-                format!("{}, {}, #{}",
-                    self.reg_names.get(&rs).unwrap(),
-                    self.reg_names.get(&rt).unwrap(),
-                    imm as i32) // Print imm as signed
-            }
-            
-        } else if instr == "BGEZ" || instr == "BGTZ" || instr == "BLEZ" || instr == "BLTZ" ||
-                  instr == "BGEZAL" || instr == "BLTZAL" {
-            // Single register branch instructions
-            format!("{}, #{}",
-                self.reg_names.get(&rs).unwrap(),
-                imm as i32) // Print imm as signed
-                
-        } else if instr == "ADDI" || instr == "ADDIU" || instr == "SLTI" || 
-                  instr == "ANDI" || instr == "ORI" || instr == "XORI" {
-            // Immediate arithmetic/logical ops
-            format!("{}, {}, #{}",
-                self.reg_names.get(&rt).unwrap(),
-                self.reg_names.get(&rs).unwrap(),
-                imm as i32) // Print imm as signed
-                
-        } else if instr == "LUI" {
-            // Load upper immediate
-            format!("{}, #{}",
-                self.reg_names.get(&rt).unwrap(),
-                imm)
-                
-        } else if instr == "LW" || instr == "LB" || instr == "LH" || instr == "LBU" || 
-                  instr == "LHU" || instr == "SW" || instr == "SB" || instr == "SH" {
-            // Memory access instructions
-            format!("{}, {}({})",
-                self.reg_names.get(&rt).unwrap(),
-                imm as i32, // Print imm as signed
-                self.reg_names.get(&rs).unwrap())
-                
-        } else {
+
+        let fields = Fields {
+            rs,
+            rt,
+            imm,
+            uimm,
+            ..Default::default()
+        };
+        let operands = operand_template(&instr)
+            .map(|tmpl| format_operands(tmpl, &fields))
             // Default for unknown instructions
-            format!("{}, {}, #{}",
-                self.reg_names.get(&rt).unwrap(),
-                self.reg_names.get(&rs).unwrap(),
-                imm as i32) // Print imm as signed
+            .unwrap_or_else(|| format!("{}, {}, #{}", reg_name(rt), reg_name(rs), imm));
+
+        Ok((instr, operands))
+    }
+
+    // Decode a COP1 (opcode 0x11) floating-point instruction. Same field
+    // layout as an R-type word, but fmt/ft/fs/fd replace rs/rt/rd/shamt,
+    // and the same funct means different mnemonics under different fmts -
+    // so the lookup key is (fmt << 8) | funct instead of funct alone.
+    fn parse_cop1_type(&self, line: usize, bin_str: &str) -> Result<(String, String), DisasmError> {
+        validate_binary(line, bin_str)?;
+
+        let fmt = u32::from_str_radix(&bin_str[6..11], 2).unwrap();
+        let ft = u32::from_str_radix(&bin_str[11..16], 2).unwrap();
+        let fs = u32::from_str_radix(&bin_str[16..21], 2).unwrap();
+        let fd = u32::from_str_radix(&bin_str[21..26], 2).unwrap();
+        let funct = u32::from_str_radix(&bin_str[26..32], 2).unwrap();
+
+        let instr = lookup_fp_type((fmt << 8) | funct).unwrap_or("UNKNOWN").to_string();
+        let fields = Fields {
+            rt: ft,
+            rd: fs,
+            shamt: fd,
+            ..Default::default()
         };
-        
-        (instr, operands)
+        let operands = operand_template(&instr)
+            .map(|tmpl| format_operands(tmpl, &fields))
+            .unwrap_or_default();
+
+        Ok((instr, operands))
     }
 
     // Decode J-type instruction
-    fn parse_j_type(&self, bin_str: &str) -> (String, String) {
+    fn parse_j_type(&self, line: usize, bin_str: &str) -> Result<(String, String), DisasmError> {
+        validate_binary(line, bin_str)?;
+
         // Extract fields
         let opcode = u32::from_str_radix(&bin_str[0..6], 2).unwrap();
         let addr = u32::from_str_radix(&bin_str[6..32], 2).unwrap() * 4; // Word-aligned
-        
-        // Get instruction name
-        let instr = match self.j_type_ops.get(&opcode) {
-            Some(name) => name.clone(),
-            None => "UNKNOWN".to_string()
+
+        let instr = lookup_j_type(opcode).unwrap_or("UNKNOWN").to_string();
+        let fields = Fields {
+            addr,
+            ..Default::default()
         };
-        
-        // J-type instructions just have a target address
-        let operands = format!("#{}", addr);
-        
-        (instr, operands)
+        let operands = operand_template(&instr)
+            .map(|tmpl| format_operands(tmpl, &fields))
+            .unwrap_or_default();
+
+        Ok((instr, operands))
     }
 
     // Identify instruction type and decode it
-    fn decode_instruction(&self, bin_str: &str) -> (String, String) {
+    fn decode_instruction(&self, line: usize, bin_str: &str) -> Result<(String, String), DisasmError> {
+        validate_binary(line, bin_str)?;
+
         // Check opcode to determine instruction type
         let opcode = u32::from_str_radix(&bin_str[0..6], 2).unwrap();
-        
+
         if opcode == 0 {
             // R-type has opcode 0
-            self.parse_r_type(bin_str)
-        } else if self.j_type_ops.contains_key(&opcode) {
-            // J-type opcodes
-            self.parse_j_type(bin_str)
+            self.parse_r_type(line, bin_str)
+        } else if opcode == 0x02 || opcode == 0x03 {
+            // J-type opcodes (J, JAL)
+            self.parse_j_type(line, bin_str)
+        } else if opcode == 0x11 {
+            // COP1 - floating-point ALU ops
+            self.parse_cop1_type(line, bin_str)
         } else {
             // Otherwise, it's an I-type
-            self.parse_i_type(bin_str)
+            self.parse_i_type(line, bin_str)
         }
     }
 
     // Main disassembly process
-    fn disassemble(&mut self) -> Result<(), io::Error> {
+    fn disassemble(&mut self) -> Result<(), DisasmError> {
         // Read binary data from file
         let binary_lines = self.load_binary()?;
-        
-        // Output lines for the file
-        let mut output_lines = Vec::new();
-        
+
+        // Decoded lines, not yet joined into their final tab-separated
+        // text - the peephole pass needs the code lines' instr/operands
+        // split apart so it can rewrite them before formatting.
+        let mut lines = Vec::new();
+
+        // Malformed words we recovered from instead of aborting
+        let mut errors = Vec::new();
+
         // Track BREAK instruction and data section
         let mut hit_break = false;
         let mut in_data_section = false;
-        
+        let mut first_is_break = false;
+
         // Process each line of binary data
-        for bin_str in &binary_lines {
+        for (i, bin_str) in binary_lines.iter().enumerate() {
+            let line = i + 1;
+
+            // A malformed word can't be decoded, so there's no instruction
+            // or value to show for it - log the error and the raw line
+            // as-is (not a fabricated 0) and move on instead of aborting
+            // the whole run.
+            if let Err(e) = validate_binary(line, bin_str) {
+                lines.push(peephole::Line::Text(format!(
+                    "{}      \t{}\t<unparseable: {}>",
+                    bin_str, self.curr_addr, e
+                )));
+                errors.push(e);
+                self.curr_addr += 4;
+                continue;
+            }
+
             // Check if we've reached data section
             if hit_break && self.curr_addr >= DATA_SECTION_ADDR && !in_data_section {
                 in_data_section = true;
             }
-            
+
             // Format binary for display
-            let formatted_bin = self.format_binary(bin_str);
-            
+            let formatted_bin = self.format_binary(line, bin_str)?;
+
             // Handle code vs. data sections
             if hit_break || in_data_section {
                 // In data section, just convert binary to decimal
                 let decimal_val = u32::from_str_radix(bin_str, 2).unwrap();
-                output_lines.push(format!("{}      \t{}\t{}", bin_str, self.curr_addr, decimal_val));
+                lines.push(peephole::Line::Text(format!(
+                    "{}      \t{}\t{}",
+                    bin_str, self.curr_addr, decimal_val
+                )));
             } else {
                 // In code section, decode instruction
-                let (instr, operands) = self.decode_instruction(bin_str);
-                
+                let (instr, operands) = self.decode_instruction(line, bin_str)?;
+
                 // Check for BREAK instruction
                 if instr == "BREAK" {
                     hit_break = true;
+                    if i == 0 {
+                        first_is_break = true;
+                    }
                 }
-                
-                // Format output line
-                if operands.is_empty() {
-                    output_lines.push(format!("{}\t{}\t{}", formatted_bin, self.curr_addr, instr));
-                } else {
-                    output_lines.push(format!("{}\t{}\t{}\t{}", formatted_bin, self.curr_addr, instr, operands));
-                }
+
+                lines.push(peephole::Line::Code {
+                    bin: formatted_bin,
+                    addr: self.curr_addr,
+                    instr,
+                    operands,
+                });
             }
-            
+
             // Move to next word (4 bytes)
             self.curr_addr += 4;
         }
-        
+
+        // Recognize pseudo-instruction idioms (MOVE, LI) before the lines
+        // take their final shape, so labels still resolve against them.
+        if self.show_pseudo_ops {
+            lines = peephole::fuse(lines);
+        }
+
+        // Join each decoded line into its final tab-separated text.
+        let output_lines: Vec<String> = lines
+            .into_iter()
+            .map(|line| match line {
+                peephole::Line::Text(text) => text,
+                peephole::Line::Code { bin, addr, instr, operands } => {
+                    if operands.is_empty() {
+                        format!("{}\t{}\t{}", bin, addr, instr)
+                    } else {
+                        format!("{}\t{}\t{}\t{}", bin, addr, instr, operands)
+                    }
+                }
+            })
+            .collect();
+
+        // Second pass: turn branch/jump targets into symbolic labels
+        let output_lines = labels::resolve(output_lines);
+
         // Write to output file
         let mut output_file = File::create(&self.output_path)?;
-        
+
         // Write output to file
         if !output_lines.is_empty() {
             // First line (needs double carriage return)
             write!(output_file, "{}\r\r\n", output_lines[0])?;
-            
+
             // Write remaining lines
             for i in 1..output_lines.len() {
                 if i == output_lines.len() - 1 {
@@ -409,17 +363,25 @@ impl MIPSDisassembler {
                 }
             }
         }
-        
+
         // Print summary
         println!("\n📊 Disassembly summary:");
         println!("  📟 Instructions processed: {}", binary_lines.len());
         println!("  💾 Output saved to: {}", self.output_path);
-        
+
         // Check if first instruction is BREAK
-        if !output_lines.is_empty() && output_lines[0].contains("BREAK") {
+        if first_is_break {
             println!("  ⚠️ WARNING: First instruction is BREAK!");
         }
-        
+
+        // Report (but don't fail on) any malformed words we recovered from
+        if !errors.is_empty() {
+            println!("  ⚠️ {} malformed line(s) skipped:", errors.len());
+            for e in &errors {
+                println!("    - {}", e);
+            }
+        }
+
         Ok(())
     }
 
@@ -458,7 +420,7 @@ fn clear_screen() {
     if cfg!(windows) {
         // Windows
         std::process::Command::new("cmd")
-            .args(&["/c", "cls"])
+            .args(["/c", "cls"])
             .status()
             .expect("Failed to clear screen");
     } else {
@@ -610,34 +572,107 @@ fn interactive_mode() {
 
 fn main() {
     let args: Vec<String> = env::args().collect();
-    
+
     // Run in interactive mode if no args provided
     if args.len() == 1 {
         interactive_mode();
         return;
     }
-    
+
+    // --assemble flips the direction: text MIPS -> binary words
+    // --emulate runs the instruction stream instead of just decoding it
+    // --no-pseudo turns off pseudo-instruction fusion in disassembly output
+    // --hex/--raw force the input format instead of guessing it from the
+    // file extension; --little-endian affects only --raw's word grouping
+    let mut assemble_mode = false;
+    let mut emulate_mode = false;
+    let mut no_pseudo_mode = false;
+    let mut hex_mode = false;
+    let mut raw_mode = false;
+    let mut little_endian = false;
+    let mut positional = Vec::new();
+
+    for arg in &args[1..] {
+        match arg.as_str() {
+            "--assemble" => assemble_mode = true,
+            "--emulate" => emulate_mode = true,
+            "--no-pseudo" => no_pseudo_mode = true,
+            "--hex" => hex_mode = true,
+            "--raw" => raw_mode = true,
+            "--little-endian" => little_endian = true,
+            other => positional.push(other.to_string()),
+        }
+    }
+
+    let endian = if little_endian { Endianness::Little } else { Endianness::Big };
+
+    // --emulate just takes one file to run; everything else takes input + output
+    if emulate_mode {
+        if positional.len() != 1 {
+            eprintln!("Error: Need an input filename.");
+            eprintln!("Usage: {} --emulate [--hex|--raw] <input_file>", args[0]);
+            process::exit(1);
+        }
+
+        let input_file = positional[0].clone();
+        if !Path::new(&input_file).exists() {
+            eprintln!("Error: Input file '{}' not found.", input_file);
+            process::exit(1);
+        }
+
+        let mut disassembler = MIPSDisassembler::new(input_file.clone(), String::new());
+        disassembler.input_format = input::detect_format(&input_file, hex_mode, raw_mode);
+        disassembler.endian = endian;
+        let words = match disassembler.load_binary() {
+            Ok(words) => words,
+            Err(e) => {
+                eprintln!("Error reading '{}': {}", input_file, e);
+                process::exit(1);
+            }
+        };
+
+        println!("⚙️ Emulating {}...", input_file);
+        let machine = emulator::run(&words);
+        machine.dump();
+        println!("\n✅ Emulation complete: {} 🎉", input_file);
+        return;
+    }
+
     // Traditional command-line mode
-    if args.len() != 3 {
+    if positional.len() != 2 {
         eprintln!("Error: Need input and output filenames.");
-        eprintln!("Usage: {} <input_file> <output_file>", args[0]);
+        eprintln!(
+            "Usage: {} [--assemble|--emulate] [--no-pseudo] [--hex|--raw] [--little-endian] <input_file> <output_file>",
+            args[0]
+        );
         eprintln!("       {}  (for interactive mode)", args[0]);
         process::exit(1);
     }
-    
+
     // Get input and output filenames
-    let input_file = args[1].clone();
-    let output_file = args[2].clone();
-    
+    let input_file = positional[0].clone();
+    let output_file = positional[1].clone();
+
     // Check if input file exists
     if !Path::new(&input_file).exists() {
         eprintln!("Error: Input file '{}' not found.", input_file);
         process::exit(1);
     }
-    
-    // Run disassembler
-    let mut disassembler = MIPSDisassembler::new(input_file.clone(), output_file.clone());
-    disassembler.run();
-    
-    println!("✅ Disassembly complete: {} → {} 🎉", input_file, output_file);
+
+    if assemble_mode {
+        // Run assembler
+        let assembler = MIPSAssembler::new(input_file.clone(), output_file.clone());
+        assembler.run();
+
+        println!("✅ Assembly complete: {} → {} 🎉", input_file, output_file);
+    } else {
+        // Run disassembler
+        let mut disassembler = MIPSDisassembler::new(input_file.clone(), output_file.clone());
+        disassembler.show_pseudo_ops = !no_pseudo_mode;
+        disassembler.input_format = input::detect_format(&input_file, hex_mode, raw_mode);
+        disassembler.endian = endian;
+        disassembler.run();
+
+        println!("✅ Disassembly complete: {} → {} 🎉", input_file, output_file);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,137 @@
+// src/peephole.rs - collapses common raw-instruction idioms into the
+// pseudo-instruction spelling a real MIPS listing would use.
+//
+// Runs as a second pass over the decoded code-section lines, after
+// decode_instruction but before they're joined into the tab-separated
+// text `disassemble` writes out (and before labels::resolve - a fused
+// LI/MOVE still branches and gets labeled the same as the raw form).
+// Single-instruction fusions (MOVE, one-word LI) just relabel the
+// mnemonic and reuse the operand text already printed; the LUI/ORI pair
+// is the only one that consumes two lines and recombines their
+// immediates into one 32-bit constant, keeping the address of the
+// first (the LUI) word.
+
+// One decoded line, carried through this pass before final formatting.
+// `Text` is a data-section/error line that's already final text and
+// just passes through untouched.
+pub enum Line {
+    Code {
+        bin: String,
+        addr: u32,
+        instr: String,
+        operands: String,
+    },
+    Text(String),
+}
+
+// Fuse recognized idioms in `lines` into their pseudo-instruction form.
+pub fn fuse(lines: Vec<Line>) -> Vec<Line> {
+    let mut out = Vec::with_capacity(lines.len());
+    let mut iter = lines.into_iter().peekable();
+
+    while let Some(line) = iter.next() {
+        let Line::Code { bin, addr, instr, operands } = line else {
+            out.push(line);
+            continue;
+        };
+
+        if instr == "LUI" {
+            if let Some(Line::Code { instr: next_instr, operands: next_operands, .. }) = iter.peek() {
+                if next_instr == "ORI" {
+                    if let Some(fused) = fuse_li_pair(&operands, next_operands) {
+                        iter.next(); // consume the ORI half
+                        out.push(Line::Code { bin, addr, instr: "LI".to_string(), operands: fused });
+                        continue;
+                    }
+                }
+            }
+        }
+
+        let (instr, operands) = fuse_single(&instr, &operands).unwrap_or((instr, operands));
+        out.push(Line::Code { bin, addr, instr, operands });
+    }
+
+    out
+}
+
+// MOVE rd, rs   <-  ADD/ADDU/OR rd, rs, R0
+// LI rt, #imm   <-  ADDIU/ORI rt, R0, #imm
+fn fuse_single(instr: &str, operands: &str) -> Option<(String, String)> {
+    let parts: Vec<&str> = operands.split(',').map(|s| s.trim()).collect();
+    if parts.len() != 3 {
+        return None;
+    }
+
+    match instr {
+        "ADD" | "ADDU" | "OR" if parts[2] == "R0" => {
+            Some(("MOVE".to_string(), format!("{}, {}", parts[0], parts[1])))
+        }
+        "ADDIU" | "ORI" if parts[1] == "R0" => {
+            Some(("LI".to_string(), format!("{}, {}", parts[0], parts[2])))
+        }
+        _ => None,
+    }
+}
+
+// LI rt, #const  <-  LUI rt, #hi  followed by  ORI rt, rt, #lo
+// Only fires when both halves target the same register; recombines the
+// hi/lo immediates into the 32-bit value the pair actually loads.
+fn fuse_li_pair(lui_operands: &str, ori_operands: &str) -> Option<String> {
+    let lui: Vec<&str> = lui_operands.split(',').map(|s| s.trim()).collect();
+    let ori: Vec<&str> = ori_operands.split(',').map(|s| s.trim()).collect();
+    if lui.len() != 2 || ori.len() != 3 {
+        return None;
+    }
+    let (lui_rt, hi_text) = (lui[0], lui[1]);
+    let (ori_rt, ori_rs, lo_text) = (ori[0], ori[1], ori[2]);
+    if lui_rt != ori_rt || lui_rt != ori_rs {
+        return None;
+    }
+
+    let hi: u32 = hi_text.trim_start_matches('#').parse().ok()?;
+    let lo_signed: i32 = lo_text.trim_start_matches('#').parse().ok()?;
+    // ORI's immediate already went through the usual 16-bit sign
+    // extension for display; recover the original bit pattern so it
+    // lands in the low 16 bits instead of sign-extending into the high
+    // ones.
+    let lo = (lo_signed as i16 as u16) as u32;
+
+    Some(format!("{}, #{}", lui_rt, (hi << 16) | lo))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fuses_add_with_zero_into_move() {
+        assert_eq!(
+            fuse_single("ADD", "R1, R2, R0"),
+            Some(("MOVE".to_string(), "R1, R2".to_string()))
+        );
+    }
+
+    #[test]
+    fn fuses_addiu_with_zero_source_into_li() {
+        assert_eq!(
+            fuse_single("ADDIU", "R1, R0, #5"),
+            Some(("LI".to_string(), "R1, #5".to_string()))
+        );
+    }
+
+    #[test]
+    fn leaves_unrelated_instructions_alone() {
+        assert_eq!(fuse_single("ADD", "R1, R2, R3"), None);
+    }
+
+    #[test]
+    fn fuses_lui_ori_pair_into_one_32_bit_li() {
+        let fused = fuse_li_pair("R1, #1", "R1, R1, #5").unwrap();
+        assert_eq!(fused, format!("R1, #{}", (1u32 << 16) | 5));
+    }
+
+    #[test]
+    fn li_pair_requires_matching_registers() {
+        assert_eq!(fuse_li_pair("R1, #1", "R2, R2, #5"), None);
+    }
+}